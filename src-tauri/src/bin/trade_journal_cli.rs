@@ -0,0 +1,63 @@
+//! Out-of-GUI companion to the Trade Journal app: connects to the backend's
+//! IPC control channel to issue one-off commands (import, export, shutdown)
+//! without opening the main window.
+
+#[path = "../ipc.rs"]
+mod ipc;
+
+use std::io::{BufRead, BufReader, Write};
+
+fn usage() -> ! {
+    eprintln!("usage: trade_journal_cli <import PATH|export PATH|shutdown>");
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = match args.next().as_deref() {
+        Some("import") => format!("{} {}", ipc::COMMAND_IMPORT, args.next().unwrap_or_else(|| usage())),
+        Some("export") => format!("{} {}", ipc::COMMAND_EXPORT, args.next().unwrap_or_else(|| usage())),
+        Some("shutdown") => ipc::COMMAND_SHUTDOWN.to_string(),
+        _ => usage(),
+    };
+
+    let app_data_dir = match ipc::app_data_dir() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("Could not resolve the app data directory");
+            std::process::exit(1);
+        }
+    };
+
+    let transport = ipc::default_transport(&app_data_dir);
+
+    match send_command(&transport, &command) {
+        Ok(response) => println!("{}", response),
+        Err(e) => {
+            eprintln!("Failed to reach the backend: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn send_command(transport: &ipc::Transport, command: &str) -> std::io::Result<String> {
+    let ipc::Transport::Socket(path) = transport;
+    let stream = std::os::unix::net::UnixStream::connect(path)?;
+    send_and_read(stream, command)
+}
+
+#[cfg(windows)]
+fn send_command(transport: &ipc::Transport, command: &str) -> std::io::Result<String> {
+    let ipc::Transport::Pipe(name) = transport;
+    let pipe = std::fs::OpenOptions::new().read(true).write(true).open(name)?;
+    send_and_read(pipe, command)
+}
+
+fn send_and_read<S: std::io::Read + std::io::Write>(mut stream: S, command: &str) -> std::io::Result<String> {
+    writeln!(stream, "{}", command)?;
+    let mut response = String::new();
+    let mut reader = BufReader::new(stream);
+    reader.read_line(&mut response)?;
+    Ok(response.trim().to_string())
+}
@@ -0,0 +1,54 @@
+//! Shared IPC types used by both the main binary and `trade_journal_cli`, so
+//! the two agree on where the backend's control channel lives and what a
+//! request line looks like without duplicating that logic in two crates.
+//! There's no shared lib target here, so this file is pulled in with
+//! `mod ipc;` from `main.rs` and `#[path = "../ipc.rs"] mod ipc;` from the
+//! CLI binary.
+
+use std::path::Path;
+#[cfg(not(windows))]
+use std::path::PathBuf;
+
+/// Name of the app data directory the IPC socket/pipe lives under. `main.rs`
+/// and `trade_journal_cli` are separate binaries that can't share an
+/// `AppHandle`, so this constant (rather than Tauri's `app_data_dir()`,
+/// which is derived from the bundle identifier and isn't visible to the
+/// CLI) is the one place both agree on where to find each other.
+pub const APP_DIR_NAME: &str = "trade-journal";
+
+/// Resolves the app data directory from `APP_DIR_NAME`, the same way both
+/// `main.rs` and `trade_journal_cli` do, so the two can never disagree about
+/// where the IPC channel lives.
+pub fn app_data_dir() -> Option<std::path::PathBuf> {
+    Some(dirs::data_dir()?.join(APP_DIR_NAME))
+}
+
+/// Where the backend's out-of-band control channel lives: a named pipe on
+/// Windows, a Unix domain socket file elsewhere. This is separate from the
+/// TCP port the web UI talks to — it exists so `trade_journal_cli` can issue
+/// one-off commands (import, export, shutdown) without a browser in the loop.
+#[derive(Clone, Debug)]
+pub enum Transport {
+    #[cfg(windows)]
+    Pipe(String),
+    #[cfg(not(windows))]
+    Socket(PathBuf),
+}
+
+/// One pipe/socket per machine is enough: only one Trade Journal backend is
+/// ever expected to run for a given user at a time.
+#[cfg(windows)]
+pub fn default_transport(_app_data_dir: &Path) -> Transport {
+    Transport::Pipe(r"\\.\pipe\trade-journal-ipc".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn default_transport(app_data_dir: &Path) -> Transport {
+    Transport::Socket(app_data_dir.join("trade-journal.sock"))
+}
+
+/// One line in, one line back. Kept deliberately simple since both ends are
+/// small, internal tools rather than a public API.
+pub const COMMAND_IMPORT: &str = "IMPORT";
+pub const COMMAND_EXPORT: &str = "EXPORT";
+pub const COMMAND_SHUTDOWN: &str = "SHUTDOWN";
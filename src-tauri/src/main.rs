@@ -1,14 +1,24 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::process::{Child, Command};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
 use std::sync::Mutex;
-use tauri::{Manager, AppHandle};
+use tauri::{AppHandle, Emitter, Manager};
 use std::thread;
 use std::time::Duration;
 
+use shared_child::SharedChild;
+
+#[cfg(not(target_os = "windows"))]
+use libc::{kill, SIGTERM};
+
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
+mod ipc;
+
 #[cfg(windows)]
 mod native_splash {
     use std::ptr::null_mut;
@@ -267,44 +277,530 @@ mod native_splash {
     }
 }
 
-struct PythonServerState {
-    child: Mutex<Option<Child>>,
-}
+// When the server is launched via `launch_server.bat` (`cmd /C ...`), the
+// real uvicorn process is a grandchild of the `Child` we hold, so killing
+// just that PID leaves uvicorn orphaned and still holding the port. This
+// walks a Toolhelp snapshot to find every transitive descendant instead.
+#[cfg(windows)]
+mod process_tree {
+    use std::collections::{HashMap, HashSet};
+    use std::mem::size_of;
+    use winapi::shared::minwindef::DWORD;
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::processthreadsapi::{OpenProcess, TerminateProcess};
+    use winapi::um::tlhelp32::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+    use winapi::um::winnt::PROCESS_TERMINATE;
 
+    /// Every (pid, parent_pid) pair currently on the system, via one
+    /// Toolhelp32 snapshot. Entries whose `szExeFile` isn't a valid
+    /// null-terminated string are skipped.
+    fn snapshot_processes() -> Vec<(DWORD, DWORD)> {
+        let mut processes = Vec::new();
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+            if snapshot == INVALID_HANDLE_VALUE {
+                return processes;
+            }
 
-#[cfg(windows)]
-fn check_python_startup_indicators(working_dir: &std::path::Path) -> (bool, String) {
-    // Check for various startup indicators
-    let db_file = working_dir.join("trade_journal.db");
-    let pid_file = working_dir.join("tauri_python_server.log");
-    
-    if db_file.exists() {
-        if let Ok(metadata) = std::fs::metadata(&db_file) {
-            if let Ok(modified) = metadata.modified() {
-                if let Ok(elapsed) = modified.elapsed() {
-                    if elapsed.as_secs() < 10 {
-                        return (true, "Database recently accessed".to_string());
+            let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+            entry.dwSize = size_of::<PROCESSENTRY32W>()
+                .try_into()
+                .expect("PROCESSENTRY32W size does not fit in a DWORD");
+
+            if Process32FirstW(snapshot, &mut entry) != 0 {
+                loop {
+                    if entry.szExeFile.iter().position(|&c| c == 0).is_some() {
+                        processes.push((entry.th32ProcessID, entry.th32ParentProcessID));
+                    }
+                    if Process32NextW(snapshot, &mut entry) == 0 {
+                        break;
                     }
                 }
             }
+
+            CloseHandle(snapshot);
         }
+        processes
     }
-    
-    if pid_file.exists() {
-        if let Ok(contents) = std::fs::read_to_string(&pid_file) {
-            if contents.contains("Starting Trade Journal") {
-                return (true, "FastAPI startup detected".to_string());
+
+    /// Every transitive descendant of `root_pid` (not including `root_pid`
+    /// itself), in top-down discovery order.
+    fn descendants_of(root_pid: DWORD, processes: &[(DWORD, DWORD)]) -> Vec<DWORD> {
+        let mut children: HashMap<DWORD, Vec<DWORD>> = HashMap::new();
+        for &(pid, parent_pid) in processes {
+            children.entry(parent_pid).or_default().push(pid);
+        }
+
+        let mut seen = HashSet::new();
+        let mut order = Vec::new();
+        let mut frontier = vec![root_pid];
+        while let Some(pid) = frontier.pop() {
+            for &child in children.get(&pid).map(Vec::as_slice).unwrap_or(&[]) {
+                if seen.insert(child) {
+                    order.push(child);
+                    frontier.push(child);
+                }
             }
-            if contents.contains("INFO") && contents.len() > 100 {
-                return (true, "Server logging active".to_string());
+        }
+        order
+    }
+
+    fn terminate_pid(pid: DWORD) {
+        unsafe {
+            let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+            if !handle.is_null() {
+                TerminateProcess(handle, 1);
+                CloseHandle(handle);
             }
         }
     }
-    
-    (false, "Starting up...".to_string())
+
+    /// Terminates every descendant of `root_pid`, bottom-up, so a worker
+    /// reparented under `cmd /C` can't outlive the child we meant to kill.
+    pub fn terminate_descendants(root_pid: DWORD) {
+        let processes = snapshot_processes();
+        let mut descendants = descendants_of(root_pid, &processes);
+        descendants.reverse();
+        for pid in descendants {
+            terminate_pid(pid);
+        }
+    }
+}
+
+struct PythonServerState {
+    // `SharedChild` (rather than `std::process::Child`) lets the watchdog
+    // thread `wait()` on the process while the window-close/exit path
+    // `kill()`s it from another thread without racing.
+    child: Mutex<Option<Arc<SharedChild>>>,
+    // Our own PID, captured at spawn time, so `verify_peer` has a cheap
+    // baseline to compare incoming loopback callers against.
+    expected_pid: u32,
+}
+
+
+// Lines emitted by uvicorn/FastAPI on the way up, and the splash progress
+// they correspond to. Order matters: the reader thread matches top-to-bottom
+// and the last one flips the "ready" signal.
+const STARTUP_MARKERS: &[(&str, &str, u32)] = &[
+    ("Started server process", "Starting server process...", 20),
+    ("Uvicorn running on", "Uvicorn running...", 33),
+    ("Application startup complete", "Application startup complete", 38),
+];
+
+const DEFAULT_PORT: u16 = 8000;
+
+/// Backend port and extra environment variables, read from the environment
+/// so two instances (or a custom deployment) don't collide on port 8000.
+#[derive(Clone)]
+struct ServerConfig {
+    port: u16,
+    extra_env: Vec<(String, String)>,
+    // Populated once `run()` has an `AppHandle` to resolve the app data dir
+    // against; `None` means TLS couldn't be set up and we've fallen back to
+    // plain HTTP rather than fail the whole startup over it.
+    tls: Option<Arc<TlsPaths>>,
+    // Where `trade_journal_cli` and the backend's control channel rendezvous.
+    // Populated the same way as `tls`, once an `AppHandle` is available.
+    ipc: Option<Arc<ipc::Transport>>,
+    // Which channel the splash-screen readiness poll waits on. TCP by
+    // default, since the webview always needs the HTTP endpoint anyway;
+    // `TRADE_JOURNAL_TRANSPORT=pipe` switches it for headless/CLI-only runs.
+    readiness_transport: ReadinessTransport,
+    // Port of the loopback `verify_peer` TCP server (see
+    // `spawn_verify_peer_server`), passed to the Python child so it has an
+    // actual channel to ask "is this caller trusted?" over.
+    verify_peer_port: Option<u16>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReadinessTransport {
+    Tcp,
+    Ipc,
+}
+
+/// Binds an ephemeral listener on 127.0.0.1, reads back the OS-assigned
+/// port, then drops the listener so the Python server can bind it instead.
+fn allocate_free_port() -> Option<u16> {
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .ok()
+        .and_then(|listener| listener.local_addr().ok())
+        .map(|addr| addr.port())
+}
+
+fn load_server_config() -> ServerConfig {
+    // An explicit port always wins; otherwise pick a free one so a second
+    // instance (or anything else already holding 8000) doesn't collide.
+    let port = std::env::var("TRADE_JOURNAL_PORT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .or_else(allocate_free_port)
+        .unwrap_or(DEFAULT_PORT);
+
+    // "KEY=VALUE,KEY2=VALUE2" forwarded to the Python child as-is.
+    let extra_env = std::env::var("TRADE_JOURNAL_EXTRA_ENV")
+        .ok()
+        .map(|raw| {
+            raw.split(',')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let readiness_transport = match std::env::var("TRADE_JOURNAL_TRANSPORT").as_deref() {
+        Ok("pipe") => ReadinessTransport::Ipc,
+        _ => ReadinessTransport::Tcp,
+    };
+
+    ServerConfig { port, extra_env, tls: None, ipc: None, readiness_transport, verify_peer_port: None }
+}
+
+fn backend_url(config: &ServerConfig, path: &str) -> String {
+    let scheme = if config.tls.is_some() { "https" } else { "http" };
+    format!("{}://localhost:{}{}", scheme, config.port, path)
+}
+
+/// Paths to the PEM cert/key the Python server should present, plus the raw
+/// cert bytes so callers can pin a `reqwest::Client` to it instead of
+/// disabling certificate verification outright.
+struct TlsPaths {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    cert_pem: Vec<u8>,
 }
 
-fn start_python_server(app_handle: &AppHandle) -> Result<Child, String> {
+/// Loads (or creates) the per-install TLS keypair used to serve the backend
+/// over `https://localhost`. A fresh cert is generated on first launch and
+/// cached in the app data dir so it survives restarts. There is no
+/// shared fallback keypair: a cert that's identical across every install
+/// would give an attacker on the same machine everything they need to MITM
+/// the loopback connection, which is worse than no TLS at all. If
+/// generation fails we return `None` and the caller falls back to plain
+/// HTTP on localhost instead.
+fn ensure_tls_cert(app_handle: &AppHandle) -> Option<TlsPaths> {
+    let cert_dir = app_handle.path().app_data_dir().ok()?.join("tls");
+    let cert_path = cert_dir.join("cert.pem");
+    let key_path = cert_dir.join("key.pem");
+
+    if !cert_path.exists() || !key_path.exists() {
+        std::fs::create_dir_all(&cert_dir).ok()?;
+
+        match rcgen::generate_simple_self_signed(vec!["localhost".to_string()]) {
+            Ok(cert) => {
+                let cert_pem = cert.cert.pem();
+                let key_pem = cert.signing_key.serialize_pem();
+                std::fs::write(&cert_path, &cert_pem).ok()?;
+                std::fs::write(&key_path, &key_pem).ok()?;
+            }
+            Err(e) => {
+                eprintln!("Failed to generate a per-install TLS cert ({}); serving the backend over plain HTTP instead", e);
+                return None;
+            }
+        }
+    }
+
+    let cert_pem = std::fs::read(&cert_path).ok()?;
+
+    // Parsed purely to fail fast (with a clear log line) if the PEM on disk
+    // is somehow malformed, the same way `rustls-pemfile` is used to load
+    // wstunnel's TLS statics.
+    let mut cert_reader = std::io::BufReader::new(cert_pem.as_slice());
+    if rustls_pemfile::certs(&mut cert_reader).count() == 0 {
+        eprintln!("TLS cert at {:?} has no parseable certificates", cert_path);
+        return None;
+    }
+    let mut key_reader = std::io::BufReader::new(std::fs::read(&key_path).ok()?.as_slice());
+    if rustls_pemfile::pkcs8_private_keys(&mut key_reader).count() == 0 {
+        eprintln!("TLS key at {:?} has no parseable PKCS#8 key", key_path);
+        return None;
+    }
+
+    Some(TlsPaths { cert_path, key_path, cert_pem })
+}
+
+/// Resolves where the IPC control channel should live and makes sure its
+/// parent directory exists (the pipe name on Windows needs no such setup).
+///
+/// This deliberately uses `ipc::app_data_dir()` rather than Tauri's
+/// `app_handle.path().app_data_dir()`: the latter is derived from the
+/// bundle identifier, which `trade_journal_cli` (a plain binary with no
+/// `AppHandle`) has no way to look up, so the two would risk resolving to
+/// different directories and never finding each other's pipe/socket.
+fn resolve_ipc_transport(_app_handle: &AppHandle) -> Option<ipc::Transport> {
+    let app_data_dir = ipc::app_data_dir()?;
+    let transport = ipc::default_transport(&app_data_dir);
+
+    #[cfg(not(windows))]
+    if let ipc::Transport::Socket(path) = &transport {
+        std::fs::create_dir_all(path.parent()?).ok()?;
+        // A stale socket file from a previous run that didn't shut down
+        // cleanly would otherwise make the backend's bind() fail.
+        let _ = std::fs::remove_file(path);
+    }
+
+    Some(transport)
+}
+
+/// A `reqwest::Client` pinned to `tls`'s cert instead of the system trust
+/// store, so self-checks (the readiness poll) succeed against our own
+/// self-signed cert without disabling verification outright.
+fn trusted_client(tls: Option<&TlsPaths>, timeout: Duration) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder().timeout(timeout);
+    if let Some(tls) = tls {
+        if let Ok(cert) = reqwest::Certificate::from_pem(&tls.cert_pem) {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Blocking counterpart of `trusted_client`, for the handful of call sites
+/// (the already-running probe, the shutdown request) that aren't async.
+fn trusted_blocking_client(tls: Option<&TlsPaths>, timeout: Duration) -> reqwest::blocking::Client {
+    let mut builder = reqwest::blocking::Client::builder().timeout(timeout);
+    if let Some(tls) = tls {
+        if let Ok(cert) = reqwest::Certificate::from_pem(&tls.cert_pem) {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    builder.build().unwrap_or_else(|_| reqwest::blocking::Client::new())
+}
+
+/// On Windows, trust the cert for the current user so the webview's chromium
+/// host loads `https://localhost` without a certificate warning. Best-effort:
+/// if `certutil` isn't available or the user declines the prompt, the window
+/// still loads (Tauri's webview falls back to its own click-through warning).
+#[cfg(windows)]
+fn trust_cert_for_webview(cert_path: &std::path::Path) {
+    let result = Command::new("certutil")
+        .args(&["-addstore", "-user", "Root", &cert_path.to_string_lossy()])
+        .output();
+    match result {
+        Ok(output) if output.status.success() => {
+            println!("Trusted {:?} in the current user's Root store", cert_path);
+        }
+        Ok(output) => eprintln!(
+            "certutil failed to trust {:?}: {}",
+            cert_path,
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(e) => eprintln!("Failed to run certutil to trust {:?}: {}", cert_path, e),
+    }
+}
+
+#[cfg(not(windows))]
+fn trust_cert_for_webview(_cert_path: &std::path::Path) {}
+
+/// Overlays our own vars on top of the inherited environment rather than
+/// clearing it, so things Python/venv/uvicorn need that we don't explicitly
+/// know about (`SystemRoot`/`TEMP`/`USERPROFILE` on Windows, `HOME` on Unix,
+/// locale vars, etc.) are still there.
+fn configure_child_env(cmd: &mut Command, config: &ServerConfig) {
+    cmd.env("PORT", config.port.to_string());
+    if let Some(tls) = &config.tls {
+        cmd.env("TLS_CERT_PATH", &tls.cert_path);
+        cmd.env("TLS_KEY_PATH", &tls.key_path);
+    }
+    if let Some(transport) = &config.ipc {
+        match transport.as_ref() {
+            #[cfg(windows)]
+            ipc::Transport::Pipe(name) => {
+                cmd.env("IPC_PIPE_PATH", name);
+            }
+            #[cfg(not(windows))]
+            ipc::Transport::Socket(path) => {
+                cmd.env("IPC_SOCKET_PATH", path);
+            }
+        }
+    }
+    if let Some(verify_peer_port) = config.verify_peer_port {
+        cmd.env("VERIFY_PEER_PORT", verify_peer_port.to_string());
+    }
+    for (key, value) in &config.extra_env {
+        cmd.env(key, value);
+    }
+}
+
+fn backend_already_running(config: &ServerConfig) -> bool {
+    trusted_blocking_client(config.tls.as_deref(), Duration::from_secs(5))
+        .get(backend_url(config, "/api/health"))
+        .send()
+        .map(|response| response.status().is_success())
+        .unwrap_or(false)
+}
+
+/// Checks whether some other process is already listening on `port`. This
+/// is a narrower, cheaper cousin of `backend_already_running`: it catches
+/// the "something non-Trade-Journal squatted the port between our
+/// allocate_free_port() probe and the Python process's bind()" case, so we
+/// can fail fast with a clear message instead of waiting out the full
+/// startup timeout.
+fn port_already_bound(port: u16) -> bool {
+    use netstat2::{iterate_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo, TcpState};
+
+    let sockets = match iterate_sockets_info(AddressFamilyFlags::all(), ProtocolFlags::TCP) {
+        Ok(sockets) => sockets,
+        Err(_) => return false,
+    };
+
+    sockets.flatten().any(|info| match info.protocol_socket_info {
+        ProtocolSocketInfo::Tcp(tcp) => tcp.local_port == port && tcp.state == TcpState::Listen,
+        _ => false,
+    })
+}
+
+// Exponential backoff with jitter for the readiness poll: start small so a
+// fast server is caught almost immediately, cap the delay so a slow one
+// doesn't go long stretches unchecked, and give up after a fixed budget.
+const READINESS_INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+const READINESS_MAX_BACKOFF: Duration = Duration::from_secs(1);
+const READINESS_BUDGET: Duration = Duration::from_secs(30);
+
+/// One HTTP probe attempt: wall-clock start/end plus how long `send()` took
+/// to get the response headers back. `connection_time` covers connecting
+/// and the request together; DNS and connect are not timed separately.
+struct RequestResult {
+    start: std::time::Instant,
+    connection_time: Duration,
+    end: std::time::Instant,
+    status: Option<reqwest::StatusCode>,
+}
+
+async fn probe_backend(client: &reqwest::Client, url: &str) -> RequestResult {
+    let start = std::time::Instant::now();
+    let connect_start = std::time::Instant::now();
+    let response = client.get(url).send().await;
+    let connection_time = connect_start.elapsed();
+    RequestResult {
+        start,
+        connection_time,
+        end: std::time::Instant::now(),
+        status: response.ok().map(|r| r.status()),
+    }
+}
+
+fn backoff_jitter() -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    Duration::from_millis((nanos % 30) as u64)
+}
+
+/// Dispatches to whichever channel `config.readiness_transport` names. The
+/// splash screen doesn't care which one it's waiting on, only that it gets
+/// the same (status, progress) callbacks and the same backoff/budget shape
+/// either way. Returns how long the backend took to become ready, for
+/// diagnosing slow cold starts, or `None` once the startup budget is spent.
+async fn wait_for_backend_ready(config: &ServerConfig, on_progress: &dyn Fn(&str, u32)) -> Option<Duration> {
+    match config.readiness_transport {
+        ReadinessTransport::Tcp => wait_for_tcp_ready(config, on_progress).await,
+        ReadinessTransport::Ipc => wait_for_ipc_ready(config, on_progress).await,
+    }
+}
+
+/// Polls `/login` (falling back to `/`) with exponential backoff until the
+/// backend responds 2xx/401, or the startup budget is exhausted.
+async fn wait_for_tcp_ready(config: &ServerConfig, on_progress: &dyn Fn(&str, u32)) -> Option<Duration> {
+    let client = trusted_client(config.tls.as_deref(), Duration::from_secs(5));
+    let overall_start = std::time::Instant::now();
+    let mut backoff = READINESS_INITIAL_BACKOFF;
+
+    loop {
+        for path in ["/login", "/"] {
+            let url = backend_url(config, path);
+            let result = probe_backend(&client, &url).await;
+            println!(
+                "Readiness probe {} -> {:?} (request {:?})",
+                url, result.status, result.connection_time
+            );
+            if let Some(status) = result.status {
+                if status.is_success() || status == reqwest::StatusCode::UNAUTHORIZED {
+                    return Some(result.end.duration_since(overall_start));
+                }
+            }
+        }
+
+        let elapsed = overall_start.elapsed();
+        if elapsed >= READINESS_BUDGET {
+            return None;
+        }
+
+        let fraction = ((elapsed.as_millis() * 52) / READINESS_BUDGET.as_millis()).min(52) as u32;
+        on_progress(&format!("Waiting for backend... ({:?} elapsed)", elapsed), 38 + fraction);
+
+        tokio::time::sleep(backoff + backoff_jitter()).await;
+        backoff = (backoff * 2).min(READINESS_MAX_BACKOFF);
+    }
+}
+
+/// Pipe/socket counterpart of `wait_for_tcp_ready`: readiness just means the
+/// backend has created the pipe/socket and is accepting connections on it,
+/// so a successful connect (no payload needed) is enough.
+async fn wait_for_ipc_ready(config: &ServerConfig, on_progress: &dyn Fn(&str, u32)) -> Option<Duration> {
+    let overall_start = std::time::Instant::now();
+    let mut backoff = READINESS_INITIAL_BACKOFF;
+
+    let Some(transport) = &config.ipc else {
+        eprintln!("Readiness transport is set to pipe mode but no IPC transport was resolved");
+        return None;
+    };
+
+    loop {
+        let connected = ipc_probe(transport);
+        println!("Readiness probe (ipc) -> connected: {}", connected);
+        if connected {
+            return Some(overall_start.elapsed());
+        }
+
+        let elapsed = overall_start.elapsed();
+        if elapsed >= READINESS_BUDGET {
+            return None;
+        }
+
+        let fraction = ((elapsed.as_millis() * 52) / READINESS_BUDGET.as_millis()).min(52) as u32;
+        on_progress(&format!("Waiting for backend... ({:?} elapsed)", elapsed), 38 + fraction);
+
+        tokio::time::sleep(backoff + backoff_jitter()).await;
+        backoff = (backoff * 2).min(READINESS_MAX_BACKOFF);
+    }
+}
+
+/// A single, non-blocking-in-spirit connect attempt against the pipe/socket.
+/// Local named pipes/Unix sockets either connect immediately or fail fast
+/// (ECONNREFUSED/ENOENT), so this is cheap enough to call inline from the
+/// async readiness loop without a dedicated blocking thread.
+fn ipc_probe(transport: &ipc::Transport) -> bool {
+    match transport {
+        #[cfg(windows)]
+        ipc::Transport::Pipe(name) => std::fs::OpenOptions::new().read(true).write(true).open(name).is_ok(),
+        #[cfg(not(windows))]
+        ipc::Transport::Socket(path) => std::os::unix::net::UnixStream::connect(path).is_ok(),
+    }
+}
+
+fn start_python_server(
+    app_handle: &AppHandle,
+    config: &ServerConfig,
+    progress_tx: mpsc::Sender<(String, u32)>,
+) -> Result<Option<Arc<SharedChild>>, String> {
+    if backend_already_running(config) {
+        println!("A Trade Journal backend is already running on port {}, attaching to it", config.port);
+        let _ = progress_tx.send(("Using already-running backend...".to_string(), 38));
+        return Ok(None);
+    }
+
+    if port_already_bound(config.port) {
+        let message = format!(
+            "Port {} is already in use by another process, cannot start the backend",
+            config.port
+        );
+        let _ = progress_tx.send((message.clone(), 16));
+        return Err(message);
+    }
+
     // Try to get the resource directory, fallback to current directory for development
     let working_dir = if let Ok(resource_dir) = app_handle.path().resource_dir() {
         println!("Resource directory from Tauri: {:?}", resource_dir);
@@ -393,151 +889,427 @@ fn start_python_server(app_handle: &AppHandle) -> Result<Child, String> {
     
     println!("Checking for launch script at: {:?}", launch_script);
     
-    // Create log file for Python output
+    // Create log file for Python output. The reader threads below forward
+    // every stdout/stderr line here as it arrives.
     let log_path = working_dir.join("tauri_python_server.log");
     let log_file = std::fs::File::create(&log_path)
         .map_err(|e| format!("Failed to create log file: {}", e))?;
     let log_file_err = log_file.try_clone()
         .map_err(|e| format!("Failed to clone log file: {}", e))?;
-    
+
     println!("Python server output will be logged to: {:?}", log_path);
-    
+
     let mut child = if launch_script.exists() {
         println!("Found launch script! Using it to start server");
         if cfg!(target_os = "windows") {
             let mut cmd = Command::new("cmd");
             cmd.arg("/C")
                 .arg(&launch_script)
-                .current_dir(&working_dir);
-            
-            // Hide console on Windows
+                .current_dir(&working_dir)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            configure_child_env(&mut cmd, config);
+
+            // Hide console on Windows, and put the child in its own process
+            // group so `GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, ...)` in
+            // `stop_python_server` has somewhere to deliver the signal --
+            // without this flag that call fails with ERROR_INVALID_PARAMETER.
             #[cfg(windows)]
             {
                 const CREATE_NO_WINDOW: u32 = 0x08000000;
-                cmd.creation_flags(CREATE_NO_WINDOW);
+                const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+                cmd.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
             }
-            
+
             cmd.spawn()
         } else {
-            Command::new("bash")
-                .arg(&launch_script)
+            let mut cmd = Command::new("bash");
+            cmd.arg(&launch_script)
                 .current_dir(&working_dir)
-                .stdout(log_file)
-                .stderr(log_file_err)
-                .spawn()
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            configure_child_env(&mut cmd, config);
+            cmd.spawn()
         }
     } else {
         println!("No launch script found, using Python directly: {}", python_cmd);
         let mut cmd = Command::new(&python_cmd);
         cmd.arg("app.py")
-            .current_dir(&working_dir);
-        
+            .current_dir(&working_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        configure_child_env(&mut cmd, config);
+
         #[cfg(windows)]
         {
             const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
+            const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+            cmd.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
         }
-        
+
         cmd.spawn()
     }
         .map_err(|e| format!("Failed to start Python server: {}. Make sure Python is installed and in PATH.", e))?;
-    
-    // Wait for server to start
-    println!("Waiting for server to start...");
-    thread::sleep(Duration::from_secs(5));
-    
-    // Check if the process is still running
-    match child.try_wait() {
-        Ok(Some(status)) => {
-            // Process exited, capture output for debugging
-            let mut stdout_str = String::new();
-            let mut stderr_str = String::new();
-            
-            if let Some(mut stdout) = child.stdout.take() {
-                use std::io::Read;
-                let _ = stdout.read_to_string(&mut stdout_str);
+
+    let stdout = child.stdout.take().ok_or("Failed to capture Python server stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture Python server stderr")?;
+
+    // Hand the (now stdio-less) child to `SharedChild` so `wait()` and
+    // `kill()` can be called concurrently from different threads.
+    let child = Arc::new(SharedChild::new(child));
+
+    // Readiness is driven by the actual uvicorn/FastAPI log lines, not a
+    // fixed sleep: the stdout reader flips `ready_tx` the moment it sees
+    // "Application startup complete".
+    let (ready_tx, ready_rx) = mpsc::channel::<()>();
+
+    {
+        let mut log_file = log_file;
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().flatten() {
+                let _ = writeln!(log_file, "{}", line);
+                for (marker, status, progress) in STARTUP_MARKERS {
+                    if line.contains(marker) {
+                        let _ = progress_tx.send((status.to_string(), *progress));
+                        if *marker == "Application startup complete" {
+                            let _ = ready_tx.send(());
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    {
+        let mut log_file_err = log_file_err;
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().flatten() {
+                let _ = writeln!(log_file_err, "{}", line);
             }
-            if let Some(mut stderr) = child.stderr.take() {
-                use std::io::Read;
-                let _ = stderr.read_to_string(&mut stderr_str);
+        });
+    }
+
+    println!("Waiting for \"Application startup complete\" in server log...");
+    match ready_rx.recv_timeout(Duration::from_secs(30)) {
+        Ok(()) => {}
+        Err(_) => {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(format!(
+                    "Python process exited early with status: {}. See {:?} for details.",
+                    status, log_path
+                ));
             }
-            
             return Err(format!(
-                "Python process exited early with status: {}\n\nSTDOUT:\n{}\n\nSTDERR:\n{}",
-                status, stdout_str, stderr_str
+                "Timed out waiting for the Python server to report startup complete. See {:?} for details.",
+                log_path
             ));
         }
-        Ok(None) => {
-            // Process is still running, good
+    }
+
+    println!("Python server started successfully");
+    Ok(Some(child))
+}
+
+
+// How long we give uvicorn to shut down cleanly (finish in-flight requests,
+// flush the SQLite DB) before we escalate to a hard kill.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Two-phase shutdown: ask the backend to drain and flush the DB on its own
+/// terms, then escalate to a hard kill if it doesn't exit within `timeout`.
+/// Returns whether it exited gracefully, so callers can log which path was
+/// taken (this matters when multiple windows could race the same state).
+fn stop_python_server(child: Arc<SharedChild>, config: &ServerConfig, timeout: Duration) -> bool {
+    println!("Stopping Python server...");
+
+    // Preferred path: POST /shutdown so uvicorn can drain in-flight requests
+    // and flush the SQLite DB on its own terms.
+    let shutdown_acknowledged = trusted_blocking_client(config.tls.as_deref(), Duration::from_secs(2))
+        .post(backend_url(config, "/shutdown"))
+        .send()
+        .ok()
+        .map(|response| response.status().is_success())
+        .unwrap_or(false);
+
+    // Fall back to a process signal if the endpoint is missing or didn't respond.
+    if !shutdown_acknowledged {
+        #[cfg(target_os = "windows")]
+        unsafe {
+            // CTRL_BREAK_EVENT is the closest Windows equivalent to SIGTERM for
+            // a console-less child: it lets uvicorn's signal handler run
+            // instead of tearing the process down immediately.
+            winapi::um::wincon::GenerateConsoleCtrlEvent(winapi::um::wincon::CTRL_BREAK_EVENT, child.id());
         }
-        Err(e) => {
-            return Err(format!("Failed to check process status: {}", e));
+
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            kill(child.id() as libc::pid_t, SIGTERM);
         }
     }
-    
-    // Check if server is responding with multiple attempts
-    let mut is_running = false;
-    for attempt in 1..=20 {
-        println!("Checking server availability (attempt {}/20)...", attempt);
-        match reqwest::blocking::get("http://localhost:8000/api/health") {
-            Ok(response) => {
-                println!("Server responded with status: {}", response.status());
-                if response.status().is_success() {
-                    is_running = true;
-                    break;
-                }
-            }
-            Err(e) => {
-                println!("Server check failed: {}", e);
-                // Also try the root endpoint
-                if let Ok(response) = reqwest::blocking::get("http://localhost:8000") {
-                    if response.status().is_success() {
-                        println!("Root endpoint responded successfully");
-                        is_running = true;
-                        break;
-                    }
-                }
+
+    // Give it a bounded window to exit on its own.
+    let deadline = std::time::Instant::now() + timeout;
+    let mut exited_gracefully = false;
+    while std::time::Instant::now() < deadline {
+        match child.try_wait() {
+            Ok(Some(_)) => {
+                exited_gracefully = true;
+                break;
             }
-        }
-        if attempt < 20 {
-            thread::sleep(Duration::from_secs(2));
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+            Err(_) => break,
         }
     }
-    
-    if !is_running {
-        return Err("Python server started but not responding properly on port 8000. Check for errors in the Python console.".to_string());
+
+    if exited_gracefully {
+        println!("Python server shut down gracefully");
+        let _ = child.wait();
+        return true;
     }
-    
-    println!("Python server started successfully");
-    Ok(child)
-}
 
+    println!("Python server did not exit within {:?}, forcing shutdown...", timeout);
 
-fn stop_python_server(mut child: Child) {
-    println!("Stopping Python server...");
-    
     #[cfg(target_os = "windows")]
     {
-        // On Windows, we need to kill the process tree
+        // `taskkill /T` can miss workers reparented under `cmd /C` (the
+        // launch_server.bat case), so walk the process tree ourselves first.
+        process_tree::terminate_descendants(child.id());
         let _ = Command::new("taskkill")
-            .args(&["/PID", &child.id().to_string(), "/T", "/F"])
+            .args(&["/PID", &child.id().to_string(), "/F"])
             .output();
     }
-    
+
     #[cfg(not(target_os = "windows"))]
     {
-        // On Unix-like systems, send SIGTERM
+        // On Unix-like systems, escalate to SIGKILL
         let _ = child.kill();
     }
-    
+
     let _ = child.wait();
     println!("Python server stopped");
+    false
+}
+
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(10);
+const WATCHDOG_MAX_RESTART_ATTEMPTS: u32 = 3;
+
+/// Periodically checks that the backend is both alive (`try_wait`) and
+/// responding (`/api/health`), and relaunches it with capped retries if
+/// either check fails. Emits `backend-status` so the frontend can react.
+fn spawn_backend_watchdog(app_handle: AppHandle, config: ServerConfig) {
+    thread::spawn(move || {
+        let mut consecutive_failures = 0u32;
+        loop {
+            thread::sleep(WATCHDOG_INTERVAL);
+
+            let state = app_handle.state::<PythonServerState>();
+            let process_alive = {
+                let guard = match state.child.lock() {
+                    Ok(guard) => guard,
+                    Err(_) => continue,
+                };
+                match guard.as_ref() {
+                    // We don't own this process (attached to an already-running
+                    // backend), so there's nothing for us to restart.
+                    None => true,
+                    Some(child) => matches!(child.try_wait(), Ok(None)),
+                }
+            };
+
+            if process_alive && backend_already_running(&config) {
+                consecutive_failures = 0;
+                continue;
+            }
+
+            eprintln!("Backend watchdog: backend unresponsive, attempting restart...");
+            let _ = app_handle.emit("backend-status", "restarting");
+
+            consecutive_failures += 1;
+            if consecutive_failures > WATCHDOG_MAX_RESTART_ATTEMPTS {
+                eprintln!(
+                    "Backend watchdog: giving up after {} failed restart attempts",
+                    WATCHDOG_MAX_RESTART_ATTEMPTS
+                );
+                let _ = app_handle.emit("backend-status", "down");
+                continue;
+            }
+
+            if let Ok(mut guard) = state.child.lock() {
+                if let Some(old_child) = guard.take() {
+                    stop_python_server(old_child, &config, DEFAULT_SHUTDOWN_TIMEOUT);
+                }
+            }
+
+            let (progress_tx, _progress_rx) = mpsc::channel::<(String, u32)>();
+            match start_python_server(&app_handle, &config, progress_tx) {
+                Ok(new_child) => {
+                    if let Ok(mut guard) = state.child.lock() {
+                        *guard = new_child;
+                    }
+                    let _ = app_handle.emit("backend-status", "ready");
+                    consecutive_failures = 0;
+                }
+                Err(e) => {
+                    eprintln!("Backend watchdog: restart failed: {}", e);
+                    let _ = app_handle.emit("backend-status", "down");
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+fn backend_status(state: tauri::State<PythonServerState>) -> &'static str {
+    let guard = match state.child.lock() {
+        Ok(guard) => guard,
+        Err(_) => return "unknown",
+    };
+    match guard.as_ref() {
+        // Attached to an already-running backend we don't own.
+        None => "running",
+        Some(child) => match child.try_wait() {
+            Ok(None) => "running",
+            _ => "stopped",
+        },
+    }
+}
+
+#[tauri::command]
+fn restart_backend(app_handle: AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<PythonServerState>();
+    let config = app_handle.state::<ServerConfig>();
+
+    {
+        let mut guard = state.child.lock().map_err(|_| "server state lock poisoned".to_string())?;
+        if let Some(child) = guard.take() {
+            stop_python_server(child, &config, DEFAULT_SHUTDOWN_TIMEOUT);
+        }
+    }
+
+    let (progress_tx, _progress_rx) = mpsc::channel::<(String, u32)>();
+    let new_child = start_python_server(&app_handle, &config, progress_tx)?;
+
+    let mut guard = state.child.lock().map_err(|_| "server state lock poisoned".to_string())?;
+    *guard = new_child;
+    drop(guard);
+
+    let _ = app_handle.emit("backend-status", "ready");
+    Ok(())
+}
+
+/// PIDs of whoever holds the loopback socket whose local port is
+/// `client_port` and whose remote port is `server_port` — i.e. the client
+/// side of the connection the Python backend just accepted.
+fn resolve_client_pids(client_port: u16, server_port: u16) -> Vec<u32> {
+    use netstat2::{iterate_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo};
+
+    let sockets = match iterate_sockets_info(AddressFamilyFlags::all(), ProtocolFlags::TCP) {
+        Ok(sockets) => sockets,
+        Err(_) => return Vec::new(),
+    };
+
+    sockets
+        .flatten()
+        .filter_map(|info| match info.protocol_socket_info {
+            ProtocolSocketInfo::Tcp(tcp) if tcp.local_port == client_port && tcp.remote_port == server_port => {
+                Some(info.associated_pids)
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// True if `pid` is our own process, or another process running the same
+/// executable as us under our own user account (e.g. a second Trade
+/// Journal window). The user check matters on shared machines: without it,
+/// a different account running the same installed binary would also pass
+/// the exe-path check and get treated as a trusted caller.
+fn is_trusted_peer(pid: u32, expected_pid: u32, our_exe: &std::path::Path) -> bool {
+    if pid == expected_pid {
+        return true;
+    }
+
+    use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    let mut system = System::new();
+    system.refresh_processes();
+
+    let our_user = system.process(Pid::from(expected_pid as usize)).and_then(|p| p.user_id().cloned());
+
+    system
+        .process(Pid::from(pid as usize))
+        .map(|process| process.exe() == our_exe && our_user.is_some() && process.user_id() == our_user.as_ref())
+        .unwrap_or(false)
+}
+
+/// Answers `"<client_port>\n"` requests with `"true\n"`/`"false\n"`, one
+/// connection per request. This is the actual channel the Python backend
+/// calls into over the loopback connection it just accepted, to ask "is
+/// this caller actually my own Tauri shell?" before serving a request — a
+/// plain `#[tauri::command]` can't be reached from outside the webview, so
+/// it alone can't stop a stray local process or browser tab from reading
+/// financial data off the API. The Python side is told where to find this
+/// server via the `VERIFY_PEER_PORT` env var (see `configure_child_env`).
+fn spawn_verify_peer_server(expected_pid: u32, server_port: u16) -> Option<u16> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).ok()?;
+    let port = listener.local_addr().ok()?.port();
+
+    thread::spawn(move || {
+        let our_exe = match std::env::current_exe() {
+            Ok(path) => path,
+            Err(_) => return,
+        };
+
+        for mut stream in listener.incoming().flatten() {
+            let our_exe = our_exe.clone();
+            thread::spawn(move || {
+                let mut line = String::new();
+                let peer = stream.try_clone().ok().map(BufReader::new);
+                let Some(mut reader) = peer else { return };
+                if reader.read_line(&mut line).is_err() {
+                    return;
+                }
+
+                let trusted = line
+                    .trim()
+                    .parse::<u16>()
+                    .ok()
+                    .map(|client_port| {
+                        resolve_client_pids(client_port, server_port)
+                            .into_iter()
+                            .any(|pid| is_trusted_peer(pid, expected_pid, &our_exe))
+                    })
+                    .unwrap_or(false);
+
+                let _ = writeln!(stream, "{}", trusted);
+            });
+        }
+    });
+
+    Some(port)
+}
+
+/// Frontend-facing equivalent of `spawn_verify_peer_server`'s check, for the
+/// webview's own sanity checks. Since this is a `#[tauri::command]` it's
+/// only reachable from our own trusted JS, not from the Python backend.
+#[tauri::command]
+fn verify_peer(state: tauri::State<PythonServerState>, client_port: u16, server_port: u16) -> bool {
+    let our_exe = match std::env::current_exe() {
+        Ok(path) => path,
+        Err(_) => return false,
+    };
+
+    resolve_client_pids(client_port, server_port)
+        .into_iter()
+        .any(|pid| is_trusted_peer(pid, state.expected_pid, &our_exe))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .invoke_handler(tauri::generate_handler![restart_backend, backend_status, verify_peer])
         .setup(|app| {
             println!("Starting Trade Journal application...");
             
@@ -557,7 +1329,7 @@ pub fn run() {
             #[cfg(not(windows))]
             let _splash: Option<()> = None;
             
-            // Update splash status and start Python server with granular progress
+            // Update splash status and start Python server
             #[cfg(windows)]
             if let Some(ref splash) = splash {
                 splash.update_status_with_progress("Finding working directory...", 5);
@@ -568,56 +1340,41 @@ pub fn run() {
                 thread::sleep(Duration::from_millis(100));
                 splash.update_status_with_progress("Starting Python process...", 15);
             }
-            
-            let python_child = match start_python_server(&app.handle()) {
-                Ok(child) => {
-                    println!("Python server started successfully");
+
+            // Real startup progress: the reader thread inside
+            // `start_python_server` pushes (status, percent) here as it
+            // recognizes uvicorn/FastAPI log lines.
+            let (progress_tx, progress_rx) = mpsc::channel::<(String, u32)>();
+            #[cfg(windows)]
+            let splash_for_progress = splash.clone();
+            thread::spawn(move || {
+                for (status, progress) in progress_rx {
                     #[cfg(windows)]
-                    if let Some(ref splash) = splash {
-                        // Get working directory for startup monitoring
-                        let working_dir = if let Ok(resource_dir) = app.handle().path().resource_dir() {
-                            if resource_dir.join("app.py").exists() {
-                                resource_dir
-                            } else {
-                                std::env::current_dir().unwrap_or_default()
-                            }
-                        } else {
-                            std::env::current_dir().unwrap_or_default()
-                        };
-                        
-                        // Detailed startup phase simulation with real indicator checks
-                        splash.update_status_with_progress("Python process started...", 18);
-                        thread::sleep(Duration::from_millis(300));
-                        
-                        splash.update_status_with_progress("Loading Python modules...", 20);
-                        thread::sleep(Duration::from_millis(800));
-                        
-                        splash.update_status_with_progress("Initializing FastAPI...", 23);
-                        thread::sleep(Duration::from_millis(600));
-                        
-                        // Check for actual startup indicators during database phase
-                        splash.update_status_with_progress("Setting up database...", 26);
-                        for i in 0..9 {
-                            thread::sleep(Duration::from_millis(100));
-                            let (detected, status) = check_python_startup_indicators(&working_dir);
-                            if detected {
-                                splash.update_status_with_progress(&status, 26 + i);
-                                break;
-                            }
-                        }
-                        
-                        splash.update_status_with_progress("Configuring middleware...", 30);
-                        thread::sleep(Duration::from_millis(400));
-                        
-                        splash.update_status_with_progress("Registering API routes...", 33);
-                        thread::sleep(Duration::from_millis(500));
-                        
-                        splash.update_status_with_progress("Binding to port 8000...", 36);
-                        thread::sleep(Duration::from_millis(600));
-                        
-                        splash.update_status_with_progress("Server startup complete", 38);
-                        thread::sleep(Duration::from_millis(400));
+                    if let Some(ref splash) = splash_for_progress {
+                        splash.update_status_with_progress(&status, progress);
                     }
+                    #[cfg(not(windows))]
+                    println!("{} ({}%)", status, progress);
+                }
+            });
+
+            let mut config = load_server_config();
+            config.tls = ensure_tls_cert(&app.handle()).map(Arc::new);
+            match &config.tls {
+                Some(tls) => trust_cert_for_webview(&tls.cert_path),
+                None => eprintln!("Could not set up TLS for the backend, falling back to plain HTTP"),
+            }
+            config.ipc = resolve_ipc_transport(&app.handle()).map(Arc::new);
+
+            let expected_pid = std::process::id();
+            config.verify_peer_port = spawn_verify_peer_server(expected_pid, config.port);
+            if config.verify_peer_port.is_none() {
+                eprintln!("Could not start the verify_peer loopback server; the backend will trust all local callers");
+            }
+
+            let python_child = match start_python_server(&app.handle(), &config, progress_tx) {
+                Ok(child) => {
+                    println!("Python server started successfully");
                     child
                 },
                 Err(e) => {
@@ -632,10 +1389,14 @@ pub fn run() {
             };
 
             let server_state = PythonServerState {
-                child: Mutex::new(Some(python_child)),
+                child: Mutex::new(python_child),
+                expected_pid,
             };
-            
+
             app.manage(server_state);
+            app.manage(config.clone());
+
+            spawn_backend_watchdog(app.handle().clone(), config.clone());
 
             let main_window = app.get_webview_window("main").unwrap();
             
@@ -644,105 +1405,52 @@ pub fn run() {
             
             #[cfg(windows)]
             let splash_for_thread = splash.clone();
-            
+
+            let config_for_readiness = config.clone();
             thread::spawn(move || {
-                // Update splash during server checks
-                for attempt in 1..=15 {
+                let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        eprintln!("Failed to start readiness-check runtime: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+
+                let on_progress = |status: &str, progress: u32| {
+                    println!("{} ({}%)", status, progress);
                     #[cfg(windows)]
                     if let Some(ref splash) = splash_for_thread {
-                        let progress = 38 + ((attempt * 52) / 15); // Progress from 38% to 90%
-                        let status = match attempt {
-                            1..=3 => format!("Waiting for server to bind... ({}/15)", attempt),
-                            4..=7 => format!("Testing HTTP endpoints... ({}/15)", attempt),
-                            8..=12 => format!("Verifying database connection... ({}/15)", attempt),
-                            _ => format!("Final connectivity check... ({}/15)", attempt),
-                        };
-                        splash.update_status_with_progress(&status, progress);
+                        splash.update_status_with_progress(status, progress);
                     }
-                    
-                    println!("Checking server health (attempt {}/15)...", attempt);
-                    
-                    // Wait before first check
-                    if attempt == 1 {
-                        thread::sleep(Duration::from_secs(3));
-                    }
-                    
-                    // Check login endpoint - returns 200 when server is ready (before authentication)
-                    match reqwest::blocking::get("http://localhost:8000/login") {
-                        Ok(response) => {
-                            if response.status().is_success() {
-                                println!("Server is fully ready!");
-                                #[cfg(windows)]
-                                if let Some(ref splash) = splash_for_thread {
-                                    splash.update_status_with_progress("Connection established!", 95);
-                                    thread::sleep(Duration::from_millis(200));
-                                    splash.update_status_with_progress("Ready!", 100);
-                                    thread::sleep(Duration::from_millis(300));
-                                }
-
-                                // Wait a bit longer to ensure server is fully ready
-                                thread::sleep(Duration::from_secs(1));
-                                let _ = main_window_clone.show();
-
-                                // Close splash after main window is shown
-                                #[cfg(windows)]
-                                if let Some(ref splash) = splash_for_thread {
-                                    thread::sleep(Duration::from_millis(200));
-                                    splash.close();
-                                }
-                                return;
-                            }
-                        }
-                        Err(_) => {
-                            // Also try the root endpoint as fallback
-                            if let Ok(response) = reqwest::blocking::get("http://localhost:8000/") {
-                                // Accept 401 from root endpoint (authentication required) as sign server is ready
-                                if response.status().is_success() || response.status() == reqwest::http::StatusCode::UNAUTHORIZED {
-                                    println!("Root endpoint responded successfully");
-                                    #[cfg(windows)]
-                                    if let Some(ref splash) = splash_for_thread {
-                                        splash.update_status_with_progress("Connection established!", 95);
-                                        thread::sleep(Duration::from_millis(200));
-                                        splash.update_status_with_progress("Ready!", 100);
-                                        thread::sleep(Duration::from_millis(300));
-                                    }
-                                    
-                                    thread::sleep(Duration::from_secs(1));
-                                    let _ = main_window_clone.show();
-                                    
-                                    // Close splash after main window is shown
-                                    #[cfg(windows)]
-                                    if let Some(ref splash) = splash_for_thread {
-                                        thread::sleep(Duration::from_millis(200));
-                                        splash.close();
-                                    }
-                                    return;
-                                }
-                            }
+                };
+
+                match runtime.block_on(wait_for_backend_ready(&config_for_readiness, &on_progress)) {
+                    Some(startup_latency) => {
+                        println!("Server is fully ready! (startup took {:?})", startup_latency);
+                        on_progress("Connection established!", 95);
+                        thread::sleep(Duration::from_millis(200));
+                        on_progress("Ready!", 100);
+                        thread::sleep(Duration::from_millis(300));
+
+                        thread::sleep(Duration::from_secs(1));
+                        let _ = main_window_clone.show();
+
+                        #[cfg(windows)]
+                        if let Some(ref splash) = splash_for_thread {
+                            thread::sleep(Duration::from_millis(200));
+                            splash.close();
                         }
                     }
-                    // Break down the 2-second wait between attempts
-                    if attempt < 15 {
-                        for _sub_wait in 1..=4 {
-                            thread::sleep(Duration::from_millis(500));
-                            #[cfg(windows)]
-                            if let Some(ref splash) = splash_for_thread {
-                                let base_progress = 38 + ((attempt * 52) / 15);
-                                let sub_progress = base_progress + (_sub_wait as u32) / 4; // Small increments during wait
-                                splash.update_status_with_progress(&format!("Waiting before retry... ({}/15)", attempt), sub_progress);
-                            }
+                    None => {
+                        eprintln!("Backend did not become ready within the startup budget");
+                        #[cfg(windows)]
+                        if let Some(ref splash) = splash_for_thread {
+                            splash.update_status("Failed to start server");
+                            thread::sleep(Duration::from_secs(3));
                         }
+                        std::process::exit(1);
                     }
                 }
-                
-                // If we get here, server failed to start
-                eprintln!("Failed to start server after 15 attempts");
-                #[cfg(windows)]
-                if let Some(ref splash) = splash_for_thread {
-                    splash.update_status("Failed to start server");
-                    thread::sleep(Duration::from_secs(3));
-                }
-                std::process::exit(1);
             });
             
             // Set up close handler to stop Python server
@@ -750,9 +1458,11 @@ pub fn run() {
             main_window.on_window_event(move |event| {
                 if let tauri::WindowEvent::CloseRequested { .. } = event {
                     let state = app_handle.state::<PythonServerState>();
+                    let config = app_handle.state::<ServerConfig>();
                     if let Ok(mut child_guard) = state.child.lock() {
                         if let Some(child) = child_guard.take() {
-                            stop_python_server(child);
+                            let graceful = stop_python_server(child, &config, DEFAULT_SHUTDOWN_TIMEOUT);
+                            println!("Backend shutdown on window close was graceful: {}", graceful);
                         }
                     };
                 }